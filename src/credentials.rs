@@ -0,0 +1,112 @@
+use anyhow::{Context, Result};
+use aws_sdk_sts as sts;
+use chrono::Local;
+use sts::operation::assume_role::AssumeRoleOutput;
+use sts::operation::assume_role_with_web_identity::AssumeRoleWithWebIdentityOutput;
+
+/// The parameters of AssumeRole.
+/// The optional parameters are omitted from the request if they are None or empty
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AssumeRoleParams {
+    /// The IAM Role ARN to assume
+    pub role_arn: String,
+    /// The duration in seconds of the role session
+    pub duration_seconds: Option<i32>,
+    /// The MFA device ARN
+    pub serial_number: Option<String>,
+    /// The TOTP code of the MFA device
+    pub token_code: Option<String>,
+    /// The inline session policy in JSON
+    pub policy: Option<String>,
+    /// The ARNs of the managed session policies
+    pub policy_arns: Vec<String>,
+    /// The role session name. default: "{timestamp}-session"
+    pub role_session_name: Option<String>,
+    /// The session tags
+    pub tags: Vec<(String, String)>,
+    /// The external ID required by the trust policy of the role
+    pub external_id: Option<String>,
+    /// The source identity, which cannot be changed in the chained role sessions
+    pub source_identity: Option<String>,
+}
+
+/// Assume the role and return the temporary credentials.
+///
+/// ```no_run
+/// # async fn example() -> anyhow::Result<()> {
+/// use aws_assume_role::credentials::{assume_role_credentials, AssumeRoleParams};
+///
+/// let config = aws_config::load_from_env().await;
+/// let sts = aws_sdk_sts::Client::new(&config);
+/// let params = AssumeRoleParams {
+///     role_arn: "arn:aws:iam::123456789012:role/Developer".to_string(),
+///     duration_seconds: Some(3600),
+///     ..Default::default()
+/// };
+/// let credentials = assume_role_credentials(params, &sts).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn assume_role_credentials(params: AssumeRoleParams, sts: &sts::Client) -> Result<sts::types::Credentials> {
+    let output = assume_role(params, sts).await?;
+    output.credentials.context("Unable to fetch temporary credentials")
+}
+
+/// Assume the role and return the whole response
+pub async fn assume_role(params: AssumeRoleParams, sts: &sts::Client) -> Result<AssumeRoleOutput> {
+    let now = Local::now().timestamp_millis();
+    let tags = params
+        .tags
+        .into_iter()
+        .map(|(key, value)| sts::types::Tag::builder().key(key).value(value).build())
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to build session tags")?;
+    let policy_arns = params
+        .policy_arns
+        .into_iter()
+        .map(|arn| sts::types::PolicyDescriptorType::builder().arn(arn).build())
+        .collect::<Vec<_>>();
+    sts.assume_role()
+        .set_role_session_name(Some(
+            params.role_session_name.unwrap_or_else(|| format!("{}-session", now)),
+        ))
+        .role_arn(params.role_arn)
+        .set_duration_seconds(params.duration_seconds)
+        .set_serial_number(params.serial_number)
+        .set_token_code(params.token_code)
+        .set_policy(params.policy)
+        .set_policy_arns((!policy_arns.is_empty()).then_some(policy_arns))
+        .set_tags((!tags.is_empty()).then_some(tags))
+        .set_external_id(params.external_id)
+        .set_source_identity(params.source_identity)
+        .send()
+        .await
+        .context("Failed to call assume_role")
+}
+
+/// Assume the role with the OIDC token such as the one of GitHub Actions instead of the AWS credentials.
+/// role_arn, duration_seconds, policy, policy_arns, and role_session_name of the parameters are used
+pub async fn assume_role_with_web_identity(
+    params: AssumeRoleParams,
+    web_identity_token: String,
+    sts: &sts::Client,
+) -> Result<AssumeRoleWithWebIdentityOutput> {
+    let now = Local::now().timestamp_millis();
+    let policy_arns = params
+        .policy_arns
+        .into_iter()
+        .map(|arn| sts::types::PolicyDescriptorType::builder().arn(arn).build())
+        .collect::<Vec<_>>();
+    sts.assume_role_with_web_identity()
+        .set_role_session_name(Some(
+            params.role_session_name.unwrap_or_else(|| format!("{}-session", now)),
+        ))
+        .role_arn(params.role_arn)
+        .web_identity_token(web_identity_token)
+        .set_duration_seconds(params.duration_seconds)
+        .set_policy(params.policy)
+        .set_policy_arns((!policy_arns.is_empty()).then_some(policy_arns))
+        .send()
+        .await
+        .context("Failed to call assume_role_with_web_identity")
+}
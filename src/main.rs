@@ -1,40 +1,53 @@
-use aws_assume_role::cli::Cli;
-use aws_config::BehaviorVersion;
-use aws_runtime::env_config::file::{EnvConfigFileKind, EnvConfigFiles};
-use aws_sdk_sts as sts;
+use aws_assume_role::cli::{redact_secrets, Cli, ExecError, EXEC_FAILURE_EXIT_CODE};
+use aws_sdk_iam as iam;
 use clap::error::ErrorKind;
 use clap::{CommandFactory, Parser};
 
 #[::tokio::main]
 async fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
     if let Err(e) = cli.validate_arguments() {
         e.exit();
     }
+    if let Err(e) = cli.apply_meta() {
+        let mut cmd = Cli::command();
+        cmd.error(ErrorKind::InvalidValue, redact_secrets(&e.to_string()))
+            .exit();
+    }
+    if let Err(e) = cli.check_region() {
+        let mut cmd = Cli::command();
+        cmd.error(ErrorKind::InvalidValue, redact_secrets(&e.to_string()))
+            .exit();
+    }
+    if let Err(e) = cli.check_role_arn() {
+        let mut cmd = Cli::command();
+        cmd.error(ErrorKind::InvalidValue, redact_secrets(&e.to_string()))
+            .exit();
+    }
 
-    let loader = aws_config::defaults(BehaviorVersion::latest());
-    let loader = match cli.aws_profile.clone() {
-        Some(profile_name) => loader.profile_name(profile_name),
-        None => loader,
-    };
-    let loader = match cli.config.clone() {
-        Some(config_path) if config_path.extension() == None => {
-            let profile_files = EnvConfigFiles::builder()
-                .with_file(EnvConfigFileKind::Config, config_path)
-                .build();
-            loader.profile_files(profile_files)
+    let config = match cli.load_sdk_config().await {
+        Ok(config) => config,
+        Err(e) => {
+            let mut cmd = Cli::command();
+            cmd.error(ErrorKind::InvalidValue, redact_secrets(&e.to_string()))
+                .exit();
         }
-        Some(_) => loader,
-        None => loader,
     };
-    let config = loader
-        .retry_config(aws_config::retry::RetryConfig::standard().with_max_attempts(3))
-        .load()
-        .await;
-    let sts = sts::Client::new(&config);
+    let sts = cli.sts_client(&config);
+    let iam = iam::Client::new(&config);
 
-    if let Err(e) = cli.execute(sts).await {
+    if let Err(e) = cli.discover_mfa(sts.clone(), iam.clone()).await {
         let mut cmd = Cli::command();
-        cmd.error(ErrorKind::Io, e.to_string()).exit();
+        cmd.error(ErrorKind::Io, redact_secrets(&format!("{:#}", e))).exit();
+    }
+
+    if let Err(e) = cli.execute(sts, iam).await {
+        let mut cmd = Cli::command();
+        let error = cmd.error(ErrorKind::Io, redact_secrets(&format!("{:#}", e)));
+        if e.is::<ExecError>() {
+            let _ = error.print();
+            std::process::exit(EXEC_FAILURE_EXIT_CODE);
+        }
+        error.exit();
     }
 }
@@ -15,6 +15,13 @@ fn no_arguments() {
     assert!(!r.is_ok());
 }
 
+#[test]
+fn list_env_vars() {
+    let cli = Cli::parse_from(["assume-role", "--list-env-vars"]);
+    let r = cli.validate_arguments();
+    assert!(r.is_ok());
+}
+
 #[rstest]
 #[case("", false)]
 #[case("--totp-code=123456", true)]
@@ -24,3 +31,109 @@ fn serial_number(#[case] arg: &str, #[case] success: bool) {
     let r = cli.validate_arguments();
     assert_eq!(r.is_ok(), success);
 }
+
+#[rstest]
+#[case("--serial-number=test_serial_number")]
+#[case("--totp-code=123456")]
+#[case("--totp-secret=secret")]
+fn no_mfa_conflicts(#[case] arg: &str) {
+    let r = Cli::try_parse_from(["assume-role", "--role-arn=test-role", "--no-mfa", arg]);
+    assert!(r.is_err());
+}
+
+#[rstest]
+#[case(&["--shell"], true)]
+#[case(&["--shell", "--prompt-marker"], true)]
+#[case(&["--shell", "--prompt-marker={role}"], true)]
+#[case(&["--prompt-marker"], false)]
+#[case(&["--shell=bash"], false)]
+#[case(&["--shell", "--format=bash"], false)]
+#[case(&["--shell", "--output-file=credentials.sh"], false)]
+#[case(&["--shell", "aws", "s3", "ls"], false)]
+#[case(&["--export=bash", "--output-file=credentials.sh"], true)]
+#[case(&["--export=json"], false)]
+fn shell(#[case] args: &[&str], #[case] success: bool) {
+    let mut argv = vec!["assume-role", "--role-arn=test-role"];
+    argv.extend(args);
+    let r = Cli::try_parse_from(argv);
+    assert_eq!(r.is_ok(), success);
+}
+
+#[rstest]
+#[case(&[r#"--policy={"Version":"2012-10-17","Statement":[]}"#], true)]
+#[case(&["--policy={"], false)]
+#[case(&["--policy={}", "--policy-file=policy.json"], false)]
+fn policy(#[case] args: &[&str], #[case] success: bool) {
+    let mut argv = vec!["assume-role", "--role-arn=test-role"];
+    argv.extend(args);
+    let r = Cli::try_parse_from(argv);
+    assert_eq!(r.is_ok(), success);
+}
+
+#[rstest]
+#[case(1, true)]
+#[case(10, true)]
+#[case(11, false)]
+fn policy_arn(#[case] n: usize, #[case] success: bool) {
+    let policy_arns = (0..n)
+        .map(|i| format!("--policy-arn=arn:aws:iam::aws:policy/Policy{}", i))
+        .collect::<Vec<_>>();
+    let mut argv = vec!["assume-role".to_string(), "--role-arn=test-role".to_string()];
+    argv.extend(policy_arns);
+    let cli = Cli::parse_from(argv);
+    let r = cli.validate_arguments();
+    assert_eq!(r.is_ok(), success);
+}
+
+#[test]
+fn role_arn_with_config() {
+    let r = Cli::try_parse_from([
+        "assume-role",
+        "--config=tests/fixtures/config.toml",
+        "--role-arn=test-role",
+    ]);
+    assert!(r.is_ok());
+}
+
+#[tokio::test]
+async fn region() {
+    let cli = Cli::parse_from([
+        "assume-role",
+        "--role-arn=test-role",
+        "--region=eu-west-1",
+        "--sts-regional-endpoints",
+    ]);
+    let config = cli.load_sdk_config().await.unwrap();
+    let client = aws_sdk_sts::Client::new(&config);
+    assert_eq!(
+        client.config().region().map(|region| region.as_ref()),
+        Some("eu-west-1")
+    );
+}
+
+#[tokio::test]
+async fn endpoint_url() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint_url = format!("--endpoint-url=http://{}", listener.local_addr().unwrap());
+    let cli = Cli::parse_from([
+        "assume-role",
+        "--role-arn=test-role",
+        "--region=us-east-1",
+        &endpoint_url,
+    ]);
+    let config = cli.load_sdk_config().await.unwrap();
+    // IAM is a global service, so the endpoint is only for STS
+    assert_eq!(config.endpoint_url(), None);
+    let credentials = aws_sdk_sts::config::Credentials::new("fake", "fake", None, None, "test");
+    let sts_config = cli
+        .sts_client(&config)
+        .config()
+        .to_builder()
+        .credentials_provider(credentials)
+        .build();
+    let client = aws_sdk_sts::Client::from_conf(sts_config);
+    let request = tokio::spawn(async move { client.get_caller_identity().send().await });
+    let accepted = tokio::time::timeout(std::time::Duration::from_secs(5), listener.accept()).await;
+    request.abort();
+    assert!(accepted.is_ok());
+}
@@ -54,13 +54,12 @@ async fn make_sts_config(container: &ContainerAsync<LocalStack>) -> Result<sts::
 
 #[rstest]
 #[case::version(vec!["--version"], true, 0)]
+#[case::list_env_vars(vec!["--list-env-vars"], true, 0)]
 #[case::no_arguments(vec![], false, 2)]
 #[case::no_such_profile(
     vec!["--config", "tests/fixtures/config.toml", "--profile-name", "no_such_profile"], false, 2)]
 #[case::coflict_role_name_and_profile_name(
     vec!["--profile-name", "test", "--role-arn", "arn:aws:iam..."], false, 2)]
-#[case::conflict_config_and_role_arn(
-    vec!["--config", "tests/fixtures/config.toml", "--role-arn", "arn:aws:iam..."], false, 2)]
 #[case::conflict_totp_secret_and_totp_code(
     vec!["--role-arn", "arn:aws:iam...", "--totp-secret", "secret", "--totp-code", "123456"], false, 2)]
 fn test_arguments(#[case] args: Vec<&str>, #[case] success: bool, #[case] code: i32) {
@@ -72,6 +71,100 @@ fn test_arguments(#[case] args: Vec<&str>, #[case] success: bool, #[case] code:
     }
 }
 
+#[rstest]
+#[case::env(vec!["list"], "arn:aws:iam::987654321234:role/TestUser")]
+#[case::role_arn(vec!["--role-arn", "arn:aws:iam::123456789012:role/Test", "--list-env-vars"], "AWS_ACCESS_KEY_ID")]
+#[case::option_wins_over_env(vec!["--config", "tests/fixtures/no_such_config.toml", "list"], "is not found")]
+fn config_from_env(#[case] args: Vec<&str>, #[case] expected: &str) {
+    let output = Command::cargo_bin("assume-role")
+        .unwrap()
+        .env("AWS_ASSUME_ROLE_CONFIG", "tests/fixtures/config.toml")
+        .args(args)
+        .output()
+        .unwrap();
+    let output = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(output.contains(expected), "{}", output);
+}
+
+#[rstest]
+#[case::default(vec![], "Migrated 2 profiles")]
+#[case::quiet(vec!["--quiet"], "")]
+fn migrate_quiet(#[case] options: Vec<&str>, #[case] expected: &str) {
+    let dir = tempfile::tempdir().unwrap();
+    let to = dir.path().join("config.toml");
+    let output = Command::cargo_bin("assume-role")
+        .unwrap()
+        .args(options)
+        .args([
+            "migrate",
+            "--from",
+            "tests/fixtures/config",
+            "--to",
+            to.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(to.exists());
+    assert!(stdout.starts_with(expected), "{}", stdout);
+    assert_eq!(stdout.is_empty(), expected.is_empty(), "{}", stdout);
+}
+
+#[rstest]
+#[case::valid(
+    "[profile.test]\nrole_arn = \"arn:aws:iam::987654321234:role/TestUser\"\n",
+    true,
+    "1 profile(s)"
+)]
+#[case::invalid(
+    "[profile.test]\nrole_arn = \"TestUser\"\n[profile.empty]\n",
+    false,
+    "2 problem(s) found"
+)]
+fn validate_config(#[case] toml_str: &str, #[case] success: bool, #[case] expected: &str) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    std::fs::write(&path, toml_str).unwrap();
+    let output = Command::cargo_bin("assume-role")
+        .unwrap()
+        .args(["--config", path.to_str().unwrap(), "validate-config"])
+        .output()
+        .unwrap();
+    let output_str = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(output.status.success(), success, "{}", output_str);
+    assert!(output_str.contains(expected), "{}", output_str);
+}
+
+#[test]
+fn aws_config_file_from_env() {
+    // $HOME/.aws/config.toml takes precedence over AWS_CONFIG_FILE, so use the empty home directory
+    let home = tempfile::tempdir().unwrap();
+    let output = Command::cargo_bin("assume-role")
+        .unwrap()
+        .env("HOME", home.path())
+        .env("AWS_CONFIG_FILE", "tests/fixtures/config")
+        .env_remove("AWS_ASSUME_ROLE_CONFIG")
+        .args(["list"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(
+        stdout.contains("arn:aws:iam::987654321234:role/AdminUser"),
+        "{}",
+        stdout
+    );
+}
+
 #[tokio::test]
 #[ignore]
 async fn format_json() -> Result<()> {
@@ -135,11 +228,43 @@ async fn format_json() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[ignore]
+async fn format_yaml() -> Result<()> {
+    let container = run_localstack().await?;
+    let endpoint_url = endpoint_url(&container).await?;
+
+    let assert = Command::cargo_bin("assume-role")
+        .unwrap()
+        .env("AWS_ACCESS_KEY_ID", "fake")
+        .env("AWS_SECRET_ACCESS_KEY", "fake")
+        .env("AWS_DEFAULT_REGION", "ap-northeast-1")
+        .env("SERIAL_NUMBER", "fake")
+        .env("TOTP_CODE", "123456")
+        .arg(format!("--endpoint-url={}", endpoint_url))
+        .arg("--format=yaml")
+        .arg("--role-arn=arn:aws:iam::123456789012:role/TestUser")
+        .assert();
+    let output = assert.get_output().to_owned();
+    assert.success().code(0);
+    let c: TemporaryCredentials = serde_yaml::from_str(&String::from_utf8(output.stdout)?)?;
+    let re_aws_access_key_id = Regex::new(r"[A-Z0-9]{20}").unwrap();
+    assert!(re_aws_access_key_id.is_match(&c.aws_access_key_id));
+    let re_aws_secret_access_key = Regex::new(r"[a-zA-Z0-9]+").unwrap();
+    assert!(re_aws_secret_access_key.is_match(&c.aws_secret_access_key));
+    assert!(c.aws_expiration.to_rfc3339().starts_with("20"));
+    assert!(c.aws_session_token.len() > 0);
+    Ok(())
+}
+
 #[rstest]
 #[case("bash", "export ")]
 #[case("zsh", "export ")]
 #[case("fish", "set -gx ")]
 #[case("power-shell", "\\$env:")]
+#[case("direnv", "export ")]
+#[case("cmd", "set \"")]
+#[case("nushell", "\\$env\\.")]
 #[tokio::test]
 #[ignore]
 async fn format_shell(#[case] shell_type: String, #[case] prefix: String) -> Result<()> {
@@ -148,12 +273,12 @@ async fn format_shell(#[case] shell_type: String, #[case] prefix: String) -> Res
 
     let assert = Command::cargo_bin("assume-role")
         .unwrap()
-        .env("AWS_ENDPOINT_URL", endpoint_url)
         .env("AWS_ACCESS_KEY_ID", "fake")
         .env("AWS_SECRET_ACCESS_KEY", "fake")
         .env("AWS_DEFAULT_REGION", "ap-northeast-1")
         .env("SERIAL_NUMBER", "fake")
         .env("TOTP_CODE", "123456")
+        .arg(format!("--endpoint-url={}", endpoint_url))
         .arg("--format")
         .arg(shell_type)
         .arg("--role-arn=arn:aws:iam::123456789012:role/TestUser")